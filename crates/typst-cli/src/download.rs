@@ -2,8 +2,14 @@
 // Closely modelled after rustup's [`DownloadTracker`].
 // https://github.com/rust-lang/rustup/blob/master/src/cli/download_tracker.rs
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{Hash, Hasher};
 use std::io::{self, ErrorKind, Read, Stderr, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use once_cell::sync::Lazy;
@@ -22,29 +28,226 @@ static CERT: Lazy<Option<Certificate>> = Lazy::new(|| {
 });
 
 /// Download binary data and display its progress.
+///
+/// If a previous attempt for the same URL left a partial download behind in
+/// the temporary directory, the transfer resumes from the byte offset that
+/// was already written to disk instead of starting over.
+///
+/// A connection lost partway through the body (not just a failure to
+/// establish it) is retried with backoff too, re-resuming from whatever
+/// made it to disk in the meantime; see [`download_resumable`].
 pub fn download_with_progress(url: &str) -> io::Result<Vec<u8>> {
-    let response = download(url)?;
-    RemoteReader::from_response(response).download()
+    let partial_path = partial_download_path(url);
+    download_resumable(url, &partial_path, |response, resume_from| {
+        RemoteReader::from_response(response, resume_from).download(&partial_path)
+    })
 }
 
-/// Download from a URL.
-pub fn download(url: &str) -> io::Result<Response> {
-    let response =
-        download_inner(url).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+/// Download a URL straight to `dest`, flushing each chunk through as it
+/// arrives instead of ever holding the full payload in memory. Useful for
+/// large files (e.g. cached package tarballs) where buffering the whole
+/// body would be wasteful.
+///
+/// If a previous attempt left a partial download behind next to `dest`, the
+/// transfer resumes from the byte offset that was already written to disk
+/// instead of starting over. A connection lost partway through the body is
+/// retried the same way; see [`download_resumable`].
+pub fn download_file_with_progress(url: &str, dest: &Path) -> io::Result<()> {
+    let partial_path = partial_file_path(dest);
+    download_resumable(url, &partial_path, |response, resume_from| {
+        RemoteReader::from_response(response, resume_from).download_to_file(dest)
+    })
+}
+
+/// Drives the full "get a response, then stream its body" cycle for one
+/// download, retrying the *whole* cycle with exponential backoff when the
+/// connection fails partway through streaming (not just when establishing
+/// it) — a dropped connection on a large archive is exactly the case this
+/// series exists for. Before each reattempt, the resume offset is
+/// re-derived from `partial_path`'s size on disk, so the retry picks up
+/// from whatever `stream` already flushed rather than starting over.
+///
+/// `stream` receives the response and the resume offset it was requested
+/// with, and is expected to drive a [`RemoteReader`] over `partial_path`.
+fn download_resumable<T>(
+    url: &str,
+    partial_path: &Path,
+    mut stream: impl FnMut(Response, u64) -> io::Result<T>,
+) -> io::Result<T> {
+    let max_attempts = crate::ARGS.retries.max(1);
+    let mut attempt = 1;
+    let mut resume_from = fs::metadata(partial_path)
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+
+    loop {
+        match download_attempt(url, resume_from) {
+            Ok(response) => match stream(response, resume_from) {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt < max_attempts && is_retryable_io_error(&err) => {
+                    std::thread::sleep(backoff_delay(attempt));
+                    attempt += 1;
+                    resume_from = fs::metadata(partial_path)
+                        .map(|meta| meta.len())
+                        .unwrap_or(0);
+                }
+                Err(err) => return Err(err),
+            },
+            Err(Attempt::Fatal(err)) => return Err(err),
+            Err(Attempt::Restart) => {
+                // The range we asked for doesn't exist on the server any
+                // more (a stale or oversized partial file, or the remote
+                // content changed size): drop it and fetch the whole body.
+                resume_from = 0;
+            }
+            Err(Attempt::Retryable { err, retry_after }) => {
+                if attempt >= max_attempts {
+                    return Err(err);
+                }
+
+                std::thread::sleep(retry_after.unwrap_or_else(|| backoff_delay(attempt)));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// The outcome of a single request attempt, classified into whether it's
+/// worth retrying.
+enum Attempt {
+    /// Not worth retrying (e.g. a `404` or a malformed URL).
+    Fatal(io::Error),
+    /// The server rejected our `Range` request (`416`): discard whatever
+    /// partial data we had and restart the download from scratch.
+    Restart,
+    /// A transient failure. `retry_after` overrides the computed backoff
+    /// when the server sent a `Retry-After` header.
+    Retryable {
+        err: io::Error,
+        retry_after: Option<Duration>,
+    },
+}
+
+/// Issue a single request and classify the result for the retry loop in
+/// [`download_resumable`].
+fn download_attempt(url: &str, resume_from: u64) -> Result<Response, Attempt> {
+    let response = download_inner(url, resume_from).map_err(|err| {
+        let retryable = err.is_timeout() || err.is_connect() || err.is_request();
+        let io_err = io::Error::other(err.to_string());
+        if retryable {
+            Attempt::Retryable {
+                err: io_err,
+                retry_after: None,
+            }
+        } else {
+            Attempt::Fatal(io_err)
+        }
+    })?;
+
     let status = response.status();
     if status.is_success() {
         Ok(response)
     } else if status == StatusCode::NOT_FOUND {
-        Err(io::ErrorKind::NotFound.into())
+        Err(Attempt::Fatal(io::ErrorKind::NotFound.into()))
+    } else if resume_from > 0 && status == StatusCode::RANGE_NOT_SATISFIABLE {
+        Err(Attempt::Restart)
+    } else if is_retryable_status(status) {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs);
+
+        Err(Attempt::Retryable {
+            err: io::Error::other(format!("{status}")),
+            retry_after,
+        })
     } else {
-        Err(io::Error::new(io::ErrorKind::Other, format!("{status}")))
+        Err(Attempt::Fatal(io::Error::other(format!("{status}"))))
     }
 }
 
+/// Whether an error from reading the response body (as opposed to one
+/// classified up front by [`download_attempt`]) is worth reconnecting and
+/// resuming for: a genuine network hiccup, or our own stall detector
+/// tripping. The overall `crate::ARGS.download_timeout` ceiling surfaces the
+/// same [`io::ErrorKind::TimedOut`] but carries a plain message rather than a
+/// [`StallError`], so it's a hard stop rather than retried here — the caller
+/// asked for that much time and no more.
+fn is_retryable_io_error(err: &io::Error) -> bool {
+    let Some(source) = err.get_ref() else {
+        return false;
+    };
+
+    source.is::<StallError>()
+        || source
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|err| err.is_timeout() || err.is_connect() || err.is_request())
+}
+
+/// Marks a stalled-connection error raised by [`RemoteReader::run`]'s own
+/// throughput check, so [`is_retryable_io_error`] can tell it apart from the
+/// overall `download_timeout` ceiling.
+#[derive(Debug)]
+struct StallError(String);
+
+impl std::fmt::Display for StallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StallError {}
+
+/// Whether an HTTP status is worth retrying: server hiccups and rate
+/// limiting, but not client errors like a bad request or missing auth.
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Base delay before the first retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound on the computed backoff, before jitter is applied.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Exponential backoff (`RETRY_BASE_DELAY * 2^(attempt - 1)`, capped at
+/// `RETRY_MAX_DELAY`) with ±25% jitter to avoid a thundering herd of
+/// simultaneous retries.
+fn backoff_delay(attempt: u32) -> Duration {
+    let exponential = RETRY_BASE_DELAY
+        .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+        .min(RETRY_MAX_DELAY);
+    exponential.mul_f64(jitter_factor())
+}
+
+/// A jitter factor in `[0.75, 1.25]`, seeded from the clock. This is just a
+/// bit of randomness to spread out retries, so it's not worth pulling in a
+/// `rand` dependency for.
+fn jitter_factor() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    0.75 + (nanos % 1000) as f64 / 1000.0 * 0.5
+}
+
 /// Internal download implementation.
-fn download_inner(url: &str) -> reqwest::Result<Response> {
-    let mut builder =
-        Client::builder().user_agent(concat!("typst/", env!("CARGO_PKG_VERSION")));
+fn download_inner(url: &str, resume_from: u64) -> reqwest::Result<Response> {
+    let mut builder = Client::builder().user_agent(concat!("typst/", env!("CARGO_PKG_VERSION")));
+
+    // No per-read timeout is configured on the client here.
+    // `RemoteReader::run`'s stall detection works on throughput and is
+    // checked after each read that returns data; it does not interrupt a
+    // single read call that blocks because the server stopped sending.
 
     // Get the network proxy config from the environment.
     if let Some(proxy) = env_proxy::for_url_str(url)
@@ -59,7 +262,263 @@ fn download_inner(url: &str) -> reqwest::Result<Response> {
         builder = builder.add_root_certificate(cert.clone());
     }
 
-    builder.build()?.get(url).send()
+    let mut request = builder.build()?.get(url);
+    if resume_from > 0 {
+        // Ask the server to pick up where the previous attempt left off. If
+        // it doesn't support range requests it will just reply with a full
+        // `200 OK`, which `RemoteReader` detects and falls back on.
+        request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+    }
+
+    request.send()
+}
+
+/// Parse the total resource size out of a `Content-Range: bytes
+/// <start>-<end>/<total>` header value.
+fn parse_content_range_total(value: &str) -> Option<usize> {
+    value.rsplit('/').next()?.parse().ok()
+}
+
+/// The path a partial download for `url` is persisted to while in progress,
+/// so that it can be resumed if the connection drops.
+fn partial_download_path(url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    std::env::temp_dir().join(format!("typst-download-{:016x}.part", hasher.finish()))
+}
+
+/// The partial-download marker sitting next to `dest`, used by
+/// [`RemoteReader::download_to_file`] so an interrupted transfer never
+/// leaves a truncated file at `dest` itself.
+fn partial_file_path(dest: &Path) -> PathBuf {
+    let mut name = dest
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Drives several concurrent downloads over a bounded worker pool and
+/// reports their combined progress as a single status line on stderr,
+/// following cargo's `PackageSet`/`Downloads` parallel-fetch model.
+pub struct Downloads<'a> {
+    urls: Vec<&'a str>,
+    workers: usize,
+}
+
+impl<'a> Downloads<'a> {
+    /// Create a download batch over `urls`, using up to 4 concurrent
+    /// connections by default.
+    pub fn new(urls: Vec<&'a str>) -> Self {
+        Self { urls, workers: 4 }
+    }
+
+    /// Bound the number of connections that run at once.
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Run every download to completion. One URL failing doesn't abort the
+    /// batch: its slot in the returned vector simply carries the error,
+    /// in the same order as the input `urls`.
+    pub fn download_all(self) -> Vec<io::Result<Vec<u8>>> {
+        let total = self.urls.len();
+        let progress = Arc::new(Mutex::new(AggregateProgress::new(total)));
+        let urls = self.urls;
+        let workers = self.workers;
+
+        run_pool(total, workers, |index| {
+            let result = download_tracked(urls[index], &progress);
+            progress.lock().unwrap().finish_one();
+            result
+        })
+    }
+}
+
+/// Runs `work` over every index in `0..len` across up to `workers`
+/// concurrent threads, picking up the next free index as each thread
+/// finishes its current one. Results come back in the original index order
+/// regardless of completion order, and one item's `work` returning an error
+/// doesn't stop the others — it just ends up in that item's slot, same as
+/// the rest of [`Downloads`]'s per-URL failure isolation.
+fn run_pool<T: Send>(len: usize, workers: usize, work: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    let next = Mutex::new(0usize);
+    let results: Vec<_> = (0..len).map(|_| Mutex::new(None)).collect();
+    let workers = workers.max(1).min(len.max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let next = &next;
+            let results = &results;
+            let work = &work;
+            scope.spawn(move || loop {
+                let index = {
+                    let mut next = next.lock().unwrap();
+                    if *next >= len {
+                        break;
+                    }
+                    let index = *next;
+                    *next += 1;
+                    index
+                };
+
+                *results[index].lock().unwrap() = Some(work(index));
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .unwrap()
+                .expect("every index is visited once")
+        })
+        .collect()
+}
+
+/// Shared progress state for a [`Downloads`] batch, aggregating byte counts
+/// across all in-flight transfers into a single status line, reusing
+/// [`as_time_unit`]/[`time_suffix`] for formatting.
+struct AggregateProgress {
+    total_downloaded: usize,
+    downloaded_this_sec: usize,
+    downloaded_last_few_secs: VecDeque<usize>,
+    completed: usize,
+    total: usize,
+    start_time: Instant,
+    last_print: Option<Instant>,
+    displayed_charcount: Option<usize>,
+    stderr: Stderr,
+}
+
+impl AggregateProgress {
+    fn new(total: usize) -> Self {
+        Self {
+            total_downloaded: 0,
+            downloaded_this_sec: 0,
+            downloaded_last_few_secs: VecDeque::with_capacity(SPEED_SAMPLES),
+            completed: 0,
+            total,
+            start_time: Instant::now(),
+            last_print: None,
+            displayed_charcount: None,
+            stderr: io::stderr(),
+        }
+    }
+
+    /// Record `n` newly downloaded bytes and refresh the status line if a
+    /// second has passed since the last refresh.
+    fn add_bytes(&mut self, n: usize) {
+        self.total_downloaded += n;
+        self.downloaded_this_sec += n;
+
+        let last_printed = *self.last_print.get_or_insert_with(Instant::now);
+
+        if Instant::now().saturating_duration_since(last_printed) >= Duration::from_secs(1) {
+            if self.downloaded_last_few_secs.len() == SPEED_SAMPLES {
+                self.downloaded_last_few_secs.pop_back();
+            }
+            self.downloaded_last_few_secs
+                .push_front(self.downloaded_this_sec);
+            self.downloaded_this_sec = 0;
+
+            self.display();
+            self.last_print = Some(Instant::now());
+        }
+    }
+
+    /// Mark one more file as finished and refresh the status line.
+    fn finish_one(&mut self) {
+        self.completed += 1;
+        self.display();
+        if self.completed == self.total {
+            let _ = writeln!(self.stderr);
+        }
+    }
+
+    /// Erase the previous line, if any, and print the current aggregate
+    /// progress in its place.
+    fn display(&mut self) {
+        if let Some(n) = self.displayed_charcount {
+            let _ = write!(self.stderr, "{}", " ".repeat(n));
+            let _ = write!(self.stderr, "\r");
+        }
+
+        let sum: usize = self.downloaded_last_few_secs.iter().sum();
+        let len = self.downloaded_last_few_secs.len();
+        let speed = sum.checked_div(len).unwrap_or(0);
+
+        let output = format!(
+            "{}/{} files, {} downloaded, {} in {}",
+            self.completed,
+            self.total,
+            as_time_unit(self.total_downloaded, false),
+            as_time_unit(speed, true),
+            time_suffix(Instant::now().saturating_duration_since(self.start_time)),
+        );
+
+        let _ = write!(self.stderr, "{output}\r");
+        self.displayed_charcount = Some(output.chars().count());
+    }
+}
+
+/// Download a single URL as part of a [`Downloads`] batch, driving a
+/// [`RemoteReader`] so it gets the exact same resume, stall-timeout and
+/// disk-backed-partial-file handling as a standalone download, just
+/// reporting its bytes into the shared `progress` tracker instead of
+/// printing its own line.
+fn download_tracked(url: &str, progress: &Mutex<AggregateProgress>) -> io::Result<Vec<u8>> {
+    let partial_path = partial_download_path(url);
+    download_resumable(url, &partial_path, |response, resume_from| {
+        RemoteReader::from_response(response, resume_from)
+            .with_aggregate_progress(progress)
+            .download(&partial_path)
+    })
+}
+
+/// Where a [`RemoteReader`] writes incoming chunks as they arrive. Modeled
+/// on cargo's `DualWriter`: the in-memory variant also mirrors every chunk
+/// to disk so the download can be resumed, while the streamed variant skips
+/// the in-memory copy entirely for callers that only care about the bytes
+/// ending up in a file.
+enum Destination {
+    /// Collects the body in memory for callers that want it back directly,
+    /// while mirroring it to `file` so a dropped connection can be resumed.
+    Buffered { data: Vec<u8>, file: File },
+    /// Writes straight through to `file`, never buffering the full payload
+    /// in memory.
+    Streamed(File),
+}
+
+impl Destination {
+    /// Append a chunk of the body to this destination, flushing the file
+    /// straight away so resumption always sees accurate progress on disk.
+    fn write_chunk(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Destination::Buffered { data, file } => {
+                data.extend_from_slice(buf);
+                file.write_all(buf)?;
+                file.flush()
+            }
+            Destination::Streamed(file) => {
+                file.write_all(buf)?;
+                file.flush()
+            }
+        }
+    }
+
+    /// Takes the in-memory buffer, if this is the [`Destination::Buffered`]
+    /// variant.
+    fn into_buffer(self) -> Option<Vec<u8>> {
+        match self {
+            Destination::Buffered { data, .. } => Some(data),
+            Destination::Streamed(_) => None,
+        }
+    }
 }
 
 /// A wrapper around [`ureq::Response`] that reads the response body in chunks
@@ -67,54 +526,147 @@ fn download_inner(url: &str) -> reqwest::Result<Response> {
 ///
 /// Downloads will _never_ fail due to statistics failing to print, print errors
 /// are silently ignored.
-struct RemoteReader {
-    response: Response,
+struct RemoteReader<'p> {
+    /// Boxed so tests can drive [`RemoteReader::run`]/[`RemoteReader::download_to_file`]
+    /// against an in-memory body instead of a live [`Response`] — nothing
+    /// past [`RemoteReader::from_response`] needs more than `Read`.
+    response: Box<dyn Read>,
     content_len: Option<usize>,
     total_downloaded: usize,
+    /// Whether the server actually honored our `Range` request (status
+    /// `206 Partial Content`). If it didn't, we're getting the whole body
+    /// from scratch and must discard whatever partial data we had.
+    resumed: bool,
     downloaded_this_sec: usize,
     downloaded_last_few_secs: VecDeque<usize>,
     start_time: Instant,
+    /// The last time the average speed was at or above
+    /// `crate::ARGS.low_speed_limit`. Used to detect a stalled connection.
+    stalled_since: Instant,
     last_print: Option<Instant>,
     displayed_charcount: Option<usize>,
     stderr: Stderr,
+    /// When set, progress is reported into this shared tracker (as part of
+    /// a [`Downloads`] batch) instead of to `stderr` directly.
+    progress: Option<&'p Mutex<AggregateProgress>>,
 }
 
-impl RemoteReader {
+impl<'p> RemoteReader<'p> {
     /// Wraps a [`ureq::Response`] and prepares it for downloading.
     ///
     /// The 'Content-Length' header is used as a size hint for read
-    /// optimization, if present.
-    pub fn from_response(response: Response) -> Self {
-        let content_len: Option<usize> = response
-            .headers()
-            .get(reqwest::header::CONTENT_LENGTH)
-            .and_then(|header| header.to_str().ok()?.parse().ok());
+    /// progress (total size, percentage and ETA), if present. `resume_from`
+    /// is the number of bytes already on disk from a previous attempt; if
+    /// the response confirms the range was honored, progress display picks
+    /// up from there.
+    pub fn from_response(response: Response, resume_from: u64) -> Self {
+        let resumed = resume_from > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+
+        let content_len: Option<usize> = if resumed {
+            // For a `206` response, `Content-Length` only covers the
+            // remaining range, so the full size has to be read out of
+            // `Content-Range: bytes <start>-<end>/<total>` instead.
+            response
+                .headers()
+                .get(reqwest::header::CONTENT_RANGE)
+                .and_then(|header| header.to_str().ok())
+                .and_then(parse_content_range_total)
+        } else {
+            response
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|header| header.to_str().ok()?.parse().ok())
+        };
 
+        let now = Instant::now();
         Self {
-            response,
+            response: Box::new(response),
             content_len,
-            total_downloaded: 0,
+            total_downloaded: if resumed { resume_from as usize } else { 0 },
+            resumed,
             downloaded_this_sec: 0,
             downloaded_last_few_secs: VecDeque::with_capacity(SPEED_SAMPLES),
-            start_time: Instant::now(),
+            start_time: now,
+            stalled_since: now,
             last_print: None,
             displayed_charcount: None,
             stderr: io::stderr(),
+            progress: None,
         }
     }
 
+    /// Report progress into `progress` (a shared [`AggregateProgress`] from
+    /// a [`Downloads`] batch) instead of printing a status line of its own.
+    pub fn with_aggregate_progress(mut self, progress: &'p Mutex<AggregateProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     /// Download the bodies content as raw bytes while attempting to print
     /// download statistics to standard error. Download progress gets displayed
     /// and updated every second.
     ///
+    /// Incoming bytes are written to `partial_path` as they arrive, appending
+    /// if the transfer was resumed or truncating otherwise, so that a
+    /// dropped connection can be picked back up on the next attempt. The
+    /// file is removed again once the download completes successfully.
+    ///
+    /// The transfer is aborted if it stalls (average speed below
+    /// `crate::ARGS.low_speed_limit` for longer than
+    /// `crate::ARGS.low_speed_timeout`) or exceeds the overall
+    /// `crate::ARGS.download_timeout`. Both checks run after each read that
+    /// returns data; a read call that blocks is not interrupted by them.
+    ///
     /// These statistics will never prevent a download from completing, errors
     /// are silently ignored.
-    pub fn download(mut self) -> io::Result<Vec<u8>> {
-        let mut buffer = vec![0; 8192];
-        let mut data = match self.content_len {
-            Some(content_len) => Vec::with_capacity(content_len),
-            None => Vec::with_capacity(8192),
+    pub fn download(mut self, partial_path: &Path) -> io::Result<Vec<u8>> {
+        // If we're resuming, the bytes already on disk won't be read again,
+        // so seed the in-memory buffer with them up front.
+        let data = if self.resumed {
+            fs::read(partial_path)?
+        } else {
+            Vec::new()
         };
+        let file = self.open_destination(partial_path)?;
+        let mut destination = Destination::Buffered { data, file };
+        self.run(&mut destination)?;
+        let _ = fs::remove_file(partial_path);
+        Ok(destination
+            .into_buffer()
+            .expect("constructed as `Buffered`"))
+    }
+
+    /// Download the body straight to a partial file next to `dest`,
+    /// flushing each chunk through as it arrives instead of ever holding
+    /// the full payload in memory, then rename it into place at `dest` once
+    /// the transfer completes.
+    ///
+    /// If the transfer stalls, times out or errors mid-stream, the partial
+    /// file is left on disk (rather than `dest`, which only ever holds a
+    /// complete download) so the next attempt can resume from it.
+    pub fn download_to_file(mut self, dest: &Path) -> io::Result<()> {
+        let partial_path = partial_file_path(dest);
+        let file = self.open_destination(&partial_path)?;
+        self.run(&mut Destination::Streamed(file))?;
+        fs::rename(&partial_path, dest)
+    }
+
+    /// Open `path` for writing, appending to it if the transfer was resumed
+    /// or truncating it otherwise.
+    fn open_destination(&self, path: &Path) -> io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(self.resumed)
+            .truncate(!self.resumed)
+            .open(path)
+    }
+
+    /// Read the response body into `destination` while attempting to print
+    /// download statistics to standard error, as described on
+    /// [`RemoteReader::download`].
+    fn run(&mut self, destination: &mut Destination) -> io::Result<()> {
+        let mut buffer = vec![0; 8192];
 
         loop {
             let read = match self.response.read(&mut buffer) {
@@ -127,7 +679,11 @@ impl RemoteReader {
                 Err(e) => return Err(e),
             };
 
-            data.extend(&buffer[..read]);
+            destination.write_chunk(&buffer[..read])?;
+
+            if let Some(progress) = self.progress {
+                progress.lock().unwrap().add_bytes(read);
+            }
 
             let last_printed = match self.last_print {
                 Some(prev) => prev,
@@ -142,28 +698,68 @@ impl RemoteReader {
             self.total_downloaded += read;
             self.downloaded_this_sec += read;
 
+            if let Some(timeout) = crate::ARGS.download_timeout {
+                if Instant::now().saturating_duration_since(self.start_time) >= timeout {
+                    return Err(io::Error::new(
+                        ErrorKind::TimedOut,
+                        format!("download exceeded the {timeout:?} timeout"),
+                    ));
+                }
+            }
+
             if elapsed >= Duration::from_secs(1) {
                 if self.downloaded_last_few_secs.len() == SPEED_SAMPLES {
                     self.downloaded_last_few_secs.pop_back();
                 }
 
-                self.downloaded_last_few_secs.push_front(self.downloaded_this_sec);
+                self.downloaded_last_few_secs
+                    .push_front(self.downloaded_this_sec);
                 self.downloaded_this_sec = 0;
 
-                if let Some(n) = self.displayed_charcount {
-                    self.erase_chars(n);
+                // A stalled connection still accepts bytes, just too slowly
+                // to ever finish; time it out rather than hang forever.
+                let speed = self.downloaded_last_few_secs.iter().sum::<usize>()
+                    / self.downloaded_last_few_secs.len();
+                if speed >= crate::ARGS.low_speed_limit {
+                    self.stalled_since = Instant::now();
+                } else if is_stalled(
+                    speed,
+                    crate::ARGS.low_speed_limit,
+                    self.stalled_since,
+                    Instant::now(),
+                    crate::ARGS.low_speed_timeout,
+                ) {
+                    return Err(io::Error::new(
+                        ErrorKind::TimedOut,
+                        StallError(format!(
+                            "download stalled: speed below {} B/s for over {:?}",
+                            crate::ARGS.low_speed_limit,
+                            crate::ARGS.low_speed_timeout
+                        )),
+                    ));
                 }
 
-                self.display();
-                let _ = write!(self.stderr, "\r");
+                // As part of a `Downloads` batch, the shared aggregate line
+                // already got these bytes above; printing our own would
+                // just interleave with the other workers' output.
+                if self.progress.is_none() {
+                    if let Some(n) = self.displayed_charcount {
+                        self.erase_chars(n);
+                    }
+
+                    self.display();
+                    let _ = write!(self.stderr, "\r");
+                }
                 self.last_print = Some(Instant::now());
             }
         }
 
-        self.display();
-        let _ = writeln!(self.stderr);
+        if self.progress.is_none() {
+            self.display();
+            let _ = writeln!(self.stderr);
+        }
 
-        Ok(data)
+        Ok(())
     }
 
     /// Compile and format several download statistics and make an attempt at
@@ -171,12 +767,13 @@ impl RemoteReader {
     fn display(&mut self) {
         let sum: usize = self.downloaded_last_few_secs.iter().sum();
         let len = self.downloaded_last_few_secs.len();
-        let speed = if len > 0 { sum / len } else { self.content_len.unwrap_or(0) };
+        let speed = sum
+            .checked_div(len)
+            .unwrap_or_else(|| self.content_len.unwrap_or(0));
 
         let total = as_time_unit(self.total_downloaded, false);
         let speed_h = as_time_unit(speed, true);
-        let elapsed =
-            time_suffix(Instant::now().saturating_duration_since(self.start_time));
+        let elapsed = time_suffix(Instant::now().saturating_duration_since(self.start_time));
 
         let output = match self.content_len {
             Some(content_len) => {
@@ -190,11 +787,9 @@ impl RemoteReader {
                     percent,
                     speed_h,
                     elapsed,
-                    time_suffix(Duration::from_secs(if speed == 0 {
-                        0
-                    } else {
-                        (remaining / speed) as u64
-                    }))
+                    time_suffix(Duration::from_secs(
+                        remaining.checked_div(speed).unwrap_or(0) as u64
+                    ))
                 )
             }
             None => format!("Total: {total} Speed: {speed_h} Elapsed: {elapsed}"),
@@ -213,6 +808,19 @@ impl RemoteReader {
     }
 }
 
+/// Whether a connection currently averaging `speed` bytes/sec counts as
+/// stalled: below `low_speed_limit` for at least `low_speed_timeout` since
+/// `stalled_since`, as observed at `now`.
+fn is_stalled(
+    speed: usize,
+    low_speed_limit: usize,
+    stalled_since: Instant,
+    now: Instant,
+    low_speed_timeout: Duration,
+) -> bool {
+    speed < low_speed_limit && now.saturating_duration_since(stalled_since) >= low_speed_timeout
+}
+
 /// Append a unit-of-time suffix.
 fn time_suffix(duration: Duration) -> String {
     let secs = duration.as_secs();
@@ -254,3 +862,246 @@ fn as_time_unit(size: usize, include_suffix: bool) -> String {
         format!("{size:3.0} B{suffix}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_content_range_total() {
+        assert_eq!(parse_content_range_total("bytes 100-199/2048"), Some(2048));
+        assert_eq!(parse_content_range_total("bytes 0-0/1"), Some(1));
+    }
+
+    #[test]
+    fn rejects_malformed_content_range() {
+        assert_eq!(parse_content_range_total("bytes 100-199/*"), None);
+        assert_eq!(parse_content_range_total(""), None);
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::OK));
+        assert!(!is_retryable_status(StatusCode::RANGE_NOT_SATISFIABLE));
+    }
+
+    #[test]
+    fn backoff_delay_stays_within_jittered_bounds() {
+        for attempt in 1..=10u32 {
+            let exponential = RETRY_BASE_DELAY
+                .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+                .min(RETRY_MAX_DELAY);
+            let delay = backoff_delay(attempt);
+            assert!(delay >= exponential.mul_f64(0.75));
+            assert!(delay <= exponential.mul_f64(1.25));
+        }
+    }
+
+    #[test]
+    fn backoff_delay_caps_at_max() {
+        // Far enough out that the exponential term would otherwise
+        // overflow/dwarf the cap.
+        let delay = backoff_delay(63);
+        assert!(delay <= RETRY_MAX_DELAY.mul_f64(1.25));
+    }
+
+    #[test]
+    fn jitter_factor_stays_within_bounds() {
+        for _ in 0..100 {
+            let factor = jitter_factor();
+            assert!((0.75..=1.25).contains(&factor), "{factor} out of bounds");
+        }
+    }
+
+    #[test]
+    fn stall_detection_waits_out_the_timeout_window() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(30);
+
+        // Below the limit, but the window hasn't elapsed yet.
+        assert!(!is_stalled(5, 10, start, start, timeout));
+        assert!(!is_stalled(
+            5,
+            10,
+            start,
+            start + Duration::from_secs(10),
+            timeout
+        ));
+
+        // Below the limit for at least the full window: stalled.
+        assert!(is_stalled(
+            5,
+            10,
+            start,
+            start + Duration::from_secs(31),
+            timeout
+        ));
+
+        // At or above the limit is never stalled, no matter how long it's
+        // been.
+        assert!(!is_stalled(
+            10,
+            10,
+            start,
+            start + Duration::from_secs(31),
+            timeout
+        ));
+        assert!(!is_stalled(
+            20,
+            10,
+            start,
+            start + Duration::from_secs(31),
+            timeout
+        ));
+    }
+
+    #[test]
+    fn aggregate_progress_tracks_bytes_and_completion() {
+        let mut progress = AggregateProgress::new(3);
+        assert_eq!(progress.total_downloaded, 0);
+        assert_eq!(progress.completed, 0);
+
+        progress.add_bytes(512);
+        progress.add_bytes(256);
+        assert_eq!(progress.total_downloaded, 768);
+        assert_eq!(progress.completed, 0);
+
+        progress.finish_one();
+        progress.finish_one();
+        assert_eq!(progress.completed, 2);
+        assert_eq!(progress.total, 3);
+
+        progress.finish_one();
+        assert_eq!(progress.completed, progress.total);
+    }
+
+    #[test]
+    fn run_pool_preserves_order_and_isolates_failures() {
+        let results = run_pool(5, 2, |index| {
+            if index == 2 {
+                Err(format!("item {index} failed"))
+            } else {
+                Ok(index * 10)
+            }
+        });
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(0),
+                Ok(10),
+                Err("item 2 failed".to_string()),
+                Ok(30),
+                Ok(40),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_pool_visits_every_index_exactly_once() {
+        let hits = run_pool(20, 4, |index| index);
+        let mut sorted = hits;
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+
+    /// An `io::Read` that always fails, standing in for a connection that
+    /// drops mid-stream.
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("connection reset"))
+        }
+    }
+
+    /// Builds a [`RemoteReader`] around an in-memory body, bypassing
+    /// [`RemoteReader::from_response`] (which needs a live [`Response`]) so
+    /// `download_to_file`'s filesystem behavior can be exercised without a
+    /// network.
+    fn test_reader(
+        response: Box<dyn Read>,
+        resumed: bool,
+        total_downloaded: usize,
+    ) -> RemoteReader<'static> {
+        let now = Instant::now();
+        RemoteReader {
+            response,
+            content_len: None,
+            total_downloaded,
+            resumed,
+            downloaded_this_sec: 0,
+            downloaded_last_few_secs: VecDeque::with_capacity(SPEED_SAMPLES),
+            start_time: now,
+            stalled_since: now,
+            last_print: None,
+            displayed_charcount: None,
+            stderr: io::stderr(),
+            progress: None,
+        }
+    }
+
+    #[test]
+    fn download_to_file_renames_into_place_only_on_success() {
+        let dest = std::env::temp_dir().join("typst-download-test-to-file-success.bin");
+        let partial = partial_file_path(&dest);
+        let _ = fs::remove_file(&dest);
+        let _ = fs::remove_file(&partial);
+
+        let reader = test_reader(Box::new(io::Cursor::new(b"hello world".to_vec())), false, 0);
+        reader.download_to_file(&dest).unwrap();
+
+        assert_eq!(fs::read(&dest).unwrap(), b"hello world");
+        assert!(
+            !partial.exists(),
+            "partial file should be renamed away, not left behind, on success"
+        );
+
+        let _ = fs::remove_file(&dest);
+    }
+
+    #[test]
+    fn download_to_file_leaves_partial_file_in_place_on_failure() {
+        let dest = std::env::temp_dir().join("typst-download-test-to-file-failure.bin");
+        let partial = partial_file_path(&dest);
+        let _ = fs::remove_file(&dest);
+        let _ = fs::remove_file(&partial);
+
+        let reader = test_reader(Box::new(FailingReader), false, 0);
+        let err = reader.download_to_file(&dest).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert!(
+            !dest.exists(),
+            "dest must never end up with a truncated/corrupt file"
+        );
+        assert!(
+            partial.exists(),
+            "the partial file should stick around so the next attempt can resume"
+        );
+
+        let _ = fs::remove_file(&partial);
+    }
+
+    #[test]
+    fn download_to_file_resumes_from_an_existing_partial_file() {
+        let dest = std::env::temp_dir().join("typst-download-test-to-file-resume.bin");
+        let partial = partial_file_path(&dest);
+        let _ = fs::remove_file(&dest);
+        fs::write(&partial, b"hello ").unwrap();
+
+        let reader = test_reader(Box::new(io::Cursor::new(b"world".to_vec())), true, 6);
+        reader.download_to_file(&dest).unwrap();
+
+        assert_eq!(fs::read(&dest).unwrap(), b"hello world");
+        assert!(!partial.exists());
+
+        let _ = fs::remove_file(&dest);
+    }
+}